@@ -1,12 +1,12 @@
 use std::collections::HashMap;
 use clap::{Parser, Subcommand, ValueEnum};
-use comfy_table::{Table, presets::UTF8_FULL};
-use chrono::{NaiveDateTime};
+use comfy_table::{Table, presets::UTF8_FULL, Attribute, Cell, Color};
+use chrono::{Datelike, NaiveDateTime};
 use regex::Regex;
+use serde::Serialize;
 use std::fs::{File, OpenOptions};
-use std::io::{self, BufRead, Write};
-use std::time::Instant;
-use lazy_static::lazy_static;
+use std::io::{self, BufRead, IsTerminal, Seek, SeekFrom, Write};
+use std::time::{Duration, Instant};
 
 #[derive(Parser)]
 #[command(author, version, about)]
@@ -14,13 +14,48 @@ struct Cli {
     #[command(subcommand)]
     command: Commands,
 
-    /// Caminho para o arquivo de log
+    /// Caminho para o(s) arquivo(s) de log: um arquivo único, um padrão glob
+    /// (ex.: "logs/app.log*") ou um diretório (combine com --recursive para
+    /// descer em subdiretórios). Arquivos rotacionados no estilo
+    /// `app.log.2020-05-11-13:22:01.123` são reconhecidos e mesclados.
     #[arg(short, long)]
     log_path: String,
 
+    /// Ao apontar --log-path para um diretório, também percorre subdiretórios
+    #[arg(short, long)]
+    recursive: bool,
+
     /// Caminho para salvar o output (opcional)
     #[arg(short, long)]
     output: Option<String>,
+
+    /// Formato do log: preset embutido (default, syslog, glog) ou "custom" para usar --log-regex/--time-format
+    #[arg(long, value_enum, default_value = "default")]
+    log_format: LogFormatPreset,
+
+    /// Regex customizada para extrair os campos do log, com grupos nomeados
+    /// `timestamp`, `level` e `message` (obrigatória quando --log-format=custom)
+    #[arg(long, required_if_eq("log_format", "custom"))]
+    log_regex: Option<String>,
+
+    /// Formato strftime usado para interpretar o grupo `timestamp` (obrigatório quando --log-format=custom)
+    #[arg(long, required_if_eq("log_format", "custom"))]
+    time_format: Option<String>,
+
+    /// Desativa a colorização da coluna de nível, mesmo em um terminal
+    #[arg(long)]
+    no_color: bool,
+
+    /// Formato de saída: tabela ASCII (padrão), JSON ou CSV
+    #[arg(long, value_enum, default_value = "table")]
+    format: OutputFormat,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    Table,
+    Json,
+    Csv,
 }
 
 #[derive(Subcommand)]
@@ -35,60 +70,229 @@ enum Commands {
         #[arg(short = 'e', long)]
         end_time: Option<String>,
 
-        /// Nível de log (ERROR, WARNING, INFO, etc.)
-        #[arg(short = 'l', long)]
+        /// Nível de log exato (ERROR, WARNING, INFO, etc.)
+        #[arg(short = 'l', long, conflicts_with = "min_level")]
         log_level: Option<LogLevel>,
 
+        /// Mantém entradas com severidade igual ou pior que o nível informado
+        /// (ex.: `--min-level warning` retorna WARNING e ERROR)
+        #[arg(long, conflicts_with = "log_level")]
+        min_level: Option<LogLevel>,
+
         /// Palavra-chave para filtrar os logs
         #[arg(short = 'k', long)]
         keyword: Option<String>,
+
+        /// Após exibir o resultado inicial, continua rodando e imprime, em
+        /// tempo real, novas linhas anexadas a --log-path que atendam aos
+        /// filtros acima (similar a `tail -f`). Detecta rotação/truncamento
+        /// reabrindo o arquivo quando seu tamanho diminui.
+        #[arg(short = 'f', long)]
+        follow: bool,
     },
 
     /// Exibe um resumo geral do arquivo de log
     Overview,
-}
 
-lazy_static! {
-    static ref LOG_REGEX: Regex = Regex::new(r"(\d{2}/\d{2}/\d{4} \d{2}:\d{2});\s*(\w+);\s*(.+)").unwrap();
+    /// Exibe tendências: histograma de entradas por intervalo de tempo,
+    /// mensagens mais frequentes e taxa de erro ao longo do tempo
+    Stats {
+        /// Tamanho do intervalo do histograma (ex.: "15m", "1h", "1d")
+        #[arg(short, long, default_value = "1h")]
+        bucket: String,
+
+        /// Quantidade de mensagens normalizadas mais frequentes a exibir
+        #[arg(short, long, default_value_t = 10)]
+        top: usize,
+    },
 }
 
 #[derive(ValueEnum, Clone, Debug)]
+enum LogFormatPreset {
+    /// `dd/mm/yyyy hh:mm;LEVEL;message` (formato original do analisador)
+    Default,
+    /// Formato de syslog clássico (BSD, RFC3164): `Mon dd hh:mm:ss host prog[pid]: message`.
+    /// Esse formato não carrega um campo de severidade estruturado, então o
+    /// nível é sempre reportado como INFO (use `glog` ou `custom` quando a
+    /// severidade real importar).
+    Syslog,
+    /// Formato glog do Google: `Lmmdd hh:mm:ss.uuuuuu thread file:line] message`
+    Glog,
+    /// Regex e formato de data fornecidos via --log-regex/--time-format
+    Custom,
+}
+
+/// Descreve como transformar uma linha de log em um `LogEntry`: a regex deve
+/// expor os grupos nomeados `timestamp` e `message`, mais um grupo `level`
+/// (a menos que `default_level` esteja definido). `time_format` é repassado
+/// para `NaiveDateTime::parse_from_str`. `short_level_codes` indica que o
+/// grupo `level` usa códigos de uma letra (ex.: glog `I/W/E/F`) que precisam
+/// ser normalizados para os nomes completos usados no resto do analisador.
+/// `default_level`, quando presente, é usado no lugar do grupo `level` para
+/// formatos que não carregam severidade estruturada (ex.: syslog clássico).
+struct LogFormat {
+    regex: Regex,
+    time_format: String,
+    short_level_codes: bool,
+    default_level: Option<String>,
+}
+
+impl LogFormat {
+    fn from_preset(preset: &LogFormatPreset, log_regex: Option<&str>, time_format: Option<&str>) -> io::Result<LogFormat> {
+        let format = match preset {
+            LogFormatPreset::Default => LogFormat {
+                regex: Regex::new(r"(?P<timestamp>\d{2}/\d{2}/\d{4} \d{2}:\d{2});\s*(?P<level>\w+);\s*(?P<message>.+)").unwrap(),
+                time_format: "%d/%m/%Y %H:%M".to_string(),
+                short_level_codes: false,
+                default_level: None,
+            },
+            LogFormatPreset::Syslog => LogFormat {
+                // `prog` opcionalmente seguido de `[pid]` (ex.: `sshd[1234]:`); sem isso,
+                // linhas comuns de sshd/systemd/cron não batiam com a regex.
+                regex: Regex::new(r"(?P<timestamp>\w{3}\s+\d{1,2} \d{2}:\d{2}:\d{2}) \S+ \S+?(?:\[\d+\])?: (?P<message>.+)").unwrap(),
+                // Syslog clássico não inclui o ano; parse_timestamp completa com o ano corrente.
+                time_format: "%b %e %H:%M:%S".to_string(),
+                short_level_codes: false,
+                // BSD syslog (RFC3164) não tem campo de severidade estruturado no corpo da
+                // linha (isso existiria no PRI do RFC5424, fora de escopo aqui).
+                default_level: Some("INFO".to_string()),
+            },
+            LogFormatPreset::Glog => LogFormat {
+                regex: Regex::new(r"(?P<level>[IWEF])(?P<timestamp>\d{4} \d{2}:\d{2}:\d{2}\.\d{6})\s+\S+\s+\S+\] (?P<message>.+)").unwrap(),
+                // Glog também não inclui o ano; idem acima.
+                time_format: "%m%d %H:%M:%S%.6f".to_string(),
+                short_level_codes: true,
+                default_level: None,
+            },
+            LogFormatPreset::Custom => LogFormat {
+                regex: Regex::new(log_regex.expect("clap garante --log-regex quando --log-format=custom"))
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, format!("--log-regex inválida: {e}")))?,
+                time_format: time_format.expect("clap garante --time-format quando --log-format=custom").to_string(),
+                short_level_codes: false,
+                default_level: None,
+            },
+        };
+
+        Ok(format)
+    }
+}
+
+/// Verifica se um formato strftime já inclui o ano (`%Y`, `%y` ou `%C`).
+fn format_has_year(time_format: &str) -> bool {
+    time_format.contains("%Y") || time_format.contains("%y") || time_format.contains("%C")
+}
+
+/// Interpreta o texto do grupo `timestamp` com `time_format`. Quando o
+/// formato não inclui o ano (ex.: presets `syslog`/`glog`), completa com o
+/// ano corrente antes de parsear, já que `NaiveDateTime::parse_from_str`
+/// exige uma data completa.
+fn parse_timestamp(raw: &str, time_format: &str) -> Option<NaiveDateTime> {
+    if format_has_year(time_format) {
+        return NaiveDateTime::parse_from_str(raw, time_format).ok();
+    }
+
+    let year = chrono::Local::now().year();
+    let augmented_format = format!("%Y {time_format}");
+    let augmented_raw = format!("{year} {raw}");
+    NaiveDateTime::parse_from_str(&augmented_raw, &augmented_format).ok()
+}
+
+/// Normaliza códigos de nível de uma letra (glog: `I/W/E/F`) para os nomes
+/// completos usados pelo resto do analisador (`LogLevel::from_log_type`,
+/// colorização, etc.).
+fn normalize_short_level(code: &str) -> String {
+    match code {
+        "I" => "INFO".to_string(),
+        "W" => "WARNING".to_string(),
+        "E" | "F" => "ERROR".to_string(),
+        "D" => "DEBUG".to_string(),
+        "T" => "TRACE".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Severidades em ordem crescente, da mais branda (`Trace`) à mais grave
+/// (`Error`), para permitir filtragem por limiar mínimo via `--min-level`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum LogLevel {
-    Error,
-    Warning,
-    Info,
-    Debug,
     Trace,
+    Debug,
+    Info,
+    Warning,
+    Error,
 }
 
-#[derive(Debug, Clone)]
+impl LogLevel {
+    /// Mapeia o texto de nível capturado na linha de log (ex.: `"ERROR"`)
+    /// de volta para a severidade correspondente, se reconhecido.
+    fn from_log_type(log_type: &str) -> Option<LogLevel> {
+        match log_type.to_uppercase().as_str() {
+            "TRACE" => Some(LogLevel::Trace),
+            "DEBUG" => Some(LogLevel::Debug),
+            "INFO" => Some(LogLevel::Info),
+            "WARNING" | "WARN" => Some(LogLevel::Warning),
+            "ERROR" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct LogEntry {
+    #[serde(serialize_with = "serialize_rfc3339")]
     pub timestamp: NaiveDateTime,
     pub log_type: String,
     pub message: String,
 }
 
+/// Serializa o timestamp (naive, sem fuso) como RFC3339 assumindo UTC, para
+/// que o export JSON/CSV produza datas que outras ferramentas reconheçam.
+fn serialize_rfc3339<S>(timestamp: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&timestamp.and_utc().to_rfc3339())
+}
+
 fn main() -> io::Result<()> {
     let start = Instant::now();
     let args = Cli::parse();
 
-    // Carrega o arquivo de log
-    let log_entries = load_logs(&args.log_path)?;
+    let format = LogFormat::from_preset(&args.log_format, args.log_regex.as_deref(), args.time_format.as_deref())?;
+
+    // Carrega o(s) arquivo(s) de log
+    let log_entries = load_logs(&args.log_path, args.recursive, &format)?;
 
     match args.command {
         Commands::Analyze {
             start_time,
             end_time,
             log_level,
+            min_level,
             keyword,
+            follow,
         } => {
+            let filter = LogFilter::new(start_time, end_time, log_level, min_level, keyword);
+
             // Filtra os logs conforme os parâmetros
-            let filtered_logs = filter_logs(&log_entries, start_time, end_time, log_level, keyword);
-            display_logs(filtered_logs, args.output);
+            let filtered_logs: Vec<&LogEntry> = log_entries.iter().filter(|log| filter.matches(log)).collect();
+            display_logs(filtered_logs, args.output.clone(), args.no_color, args.format);
+
+            if follow {
+                let target = resolve_follow_target(&args.log_path, args.recursive)?;
+                follow_logs(&target, &format, &filter, args.no_color)?;
+            }
         },
         Commands::Overview => {
             let summary = summarize_logs(&log_entries);
-            display_overview(summary);
+            display_overview(summary, args.format, args.output);
+        }
+        Commands::Stats { bucket, top } => {
+            let bucket_duration = parse_bucket(&bucket).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, format!("--bucket inválido: \"{bucket}\" (use algo como 15m, 1h ou 1d)"))
+            })?;
+            let report = compute_stats(&log_entries, bucket_duration, top);
+            display_stats(report, args.format, args.output);
         }
     }
 
@@ -97,82 +301,298 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
-fn load_logs(log_path: &str) -> io::Result<Vec<LogEntry>> {
-    let file = File::open(log_path)?;
-    let reader = io::BufReader::new(file);
+/// Reconhece sufixos de rotação no estilo TiKV, ex.:
+/// `app.log.2020-05-11-13:22:01.123`.
+fn rotated_suffix_regex() -> &'static Regex {
+    static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\.\d{4}-\d{2}-\d{2}-\d{2}:\d{2}:\d{2}(\.\d+)?$").unwrap())
+}
+
+fn walk_dir(dir: &std::path::Path, recursive: bool, files: &mut Vec<std::path::PathBuf>) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            if recursive {
+                walk_dir(&path, recursive, files)?;
+            }
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Expande `log_path` em uma lista concreta de arquivos: aceita um arquivo
+/// único (incluindo seus irmãos rotacionados, ex.: `app.log.2020-...`), um
+/// padrão glob (ex.: `logs/*.log`) ou um diretório (percorrido
+/// recursivamente com `--recursive`).
+fn resolve_log_files(log_path: &str, recursive: bool) -> io::Result<Vec<std::path::PathBuf>> {
+    let path = std::path::Path::new(log_path);
+
+    if path.is_dir() {
+        let mut files = Vec::new();
+        walk_dir(path, recursive, &mut files)?;
+        return Ok(files);
+    }
+
+    if log_path.contains(['*', '?', '[']) {
+        let files: Vec<_> = glob::glob(log_path)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+            .filter_map(Result::ok)
+            .filter(|p| p.is_file())
+            .collect();
+        return Ok(files);
+    }
+
+    let mut files = vec![path.to_path_buf()];
+    if let (Some(dir), Some(file_name)) = (path.parent(), path.file_name().and_then(|n| n.to_str())) {
+        let dir = if dir.as_os_str().is_empty() { std::path::Path::new(".") } else { dir };
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.filter_map(Result::ok) {
+                let candidate = entry.path();
+                if let Some(candidate_name) = candidate.file_name().and_then(|n| n.to_str()) {
+                    if candidate_name != file_name
+                        && candidate_name.starts_with(file_name)
+                        && rotated_suffix_regex().is_match(candidate_name)
+                    {
+                        files.push(candidate);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+fn load_logs(log_path: &str, recursive: bool, format: &LogFormat) -> io::Result<Vec<LogEntry>> {
     let mut logs = Vec::new();
 
-    for line in reader.lines() {
-        let line = line?;
-        if let Some(log_entry) = parse_log_line(&line) {
-            logs.push(log_entry);
+    for path in resolve_log_files(log_path, recursive)? {
+        let file = File::open(&path)?;
+        let reader = io::BufReader::new(file);
+
+        for line in reader.lines() {
+            let line = line?;
+            if let Some(log_entry) = parse_log_line(&line, format) {
+                logs.push(log_entry);
+            }
         }
     }
 
+    // Mescla as entradas de todos os arquivos (inclusive rotacionados) em uma
+    // única linha do tempo ordenada.
+    logs.sort_by_key(|entry| entry.timestamp);
+
     Ok(logs)
 }
 
-fn parse_log_line(line: &str) -> Option<LogEntry> {
-    // Assumindo um formato de log "dd/mm/yyyy hh:mm LEVEL Mensagem"
-    if let Some(caps) = LOG_REGEX.captures(line) {
-        let timestamp = NaiveDateTime::parse_from_str(&caps[1], "%d/%m/%Y %H:%M").ok()?;
-        let log_type = caps[2].to_string();
-        let message = caps[3].to_string();
-
-        Some(LogEntry { timestamp, log_type, message })
+fn parse_log_line(line: &str, format: &LogFormat) -> Option<LogEntry> {
+    let caps = format.regex.captures(line)?;
+    let timestamp = parse_timestamp(&caps["timestamp"], &format.time_format)?;
+    let log_type = if let Some(default_level) = &format.default_level {
+        default_level.clone()
+    } else if format.short_level_codes {
+        normalize_short_level(&caps["level"])
     } else {
-        None
+        caps["level"].to_string()
+    };
+    let message = caps["message"].to_string();
+
+    Some(LogEntry { timestamp, log_type, message })
+}
+
+/// Critérios de filtragem já normalizados (datas parseadas, nível em
+/// maiúsculas, etc.), reutilizados tanto pelo modo de análise em lote
+/// (subcomando `Analyze`) quanto pelo modo `--follow`, que testa uma entrada por vez.
+struct LogFilter {
+    start_dt: Option<NaiveDateTime>,
+    end_dt: Option<NaiveDateTime>,
+    level_str: Option<String>,
+    min_level: Option<LogLevel>,
+    keyword: String,
+}
+
+impl LogFilter {
+    fn new(
+        start_time: Option<String>,
+        end_time: Option<String>,
+        log_level: Option<LogLevel>,
+        min_level: Option<LogLevel>,
+        keyword: Option<String>,
+    ) -> LogFilter {
+        LogFilter {
+            start_dt: start_time.and_then(|s| NaiveDateTime::parse_from_str(&s, "%d/%m/%Y %H:%M").ok()),
+            end_dt: end_time.and_then(|e| NaiveDateTime::parse_from_str(&e, "%d/%m/%Y %H:%M").ok()),
+            level_str: log_level.map(|level| format!("{:?}", level).to_uppercase()),
+            min_level,
+            keyword: keyword.unwrap_or_default(),
+        }
+    }
+
+    fn matches(&self, log: &LogEntry) -> bool {
+        let mut valid = true;
+
+        if let Some(start) = self.start_dt {
+            valid &= log.timestamp >= start;
+        }
+        if let Some(end) = self.end_dt {
+            valid &= log.timestamp <= end;
+        }
+        if let Some(level) = &self.level_str {
+            valid &= log.log_type == *level;
+        }
+        if let Some(min) = self.min_level {
+            valid &= LogLevel::from_log_type(&log.log_type).is_some_and(|entry_level| entry_level >= min);
+        }
+        if !self.keyword.is_empty() && !log.message.contains(&self.keyword) {
+            valid &= false;
+        }
+
+        valid
     }
 }
 
-fn filter_logs<'a>(
-    logs: &'a [LogEntry],
-    start_time: Option<String>,
-    end_time: Option<String>,
-    log_level: Option<LogLevel>,
-    keyword: Option<String>,
-) -> Vec<&'a LogEntry> {
-    let start_dt = start_time.and_then(|s| NaiveDateTime::parse_from_str(&s, "%d/%m/%Y %H:%M").ok());
-    let end_dt = end_time.and_then(|e| NaiveDateTime::parse_from_str(&e, "%d/%m/%Y %H:%M").ok());
-    let level_str = log_level.map(|level| format!("{:?}", level).to_uppercase());
-    let keyword_str = keyword.unwrap_or_default();
 
-    logs.iter()
-        .filter(|log| {
-            let mut valid = true;
+/// Decide se a saída deve ser colorida: respeita `--no-color`, a variável de
+/// ambiente `NO_COLOR` e só coloriza quando a saída padrão é um terminal
+/// (nunca quando o resultado é salvo em arquivo via `--output`).
+fn should_colorize(no_color: bool, output: &Option<String>) -> bool {
+    !no_color
+        && std::env::var_os("NO_COLOR").is_none()
+        && output.is_none()
+        && io::stdout().is_terminal()
+}
 
-            if let Some(start) = start_dt {
-                valid &= log.timestamp >= start;
-            }
-            if let Some(end) = end_dt {
-                valid &= log.timestamp <= end;
-            }
-            if let Some(level) = &level_str {
-                valid &= log.log_type == *level;
-            }
-            if !keyword_str.is_empty() && !log.message.contains(&keyword_str) {
-                valid &= false;
-            }
+fn level_color(log_type: &str) -> Option<Color> {
+    match log_type.to_uppercase().as_str() {
+        "ERROR" => Some(Color::Red),
+        "WARNING" | "WARN" => Some(Color::Yellow),
+        "INFO" => Some(Color::Green),
+        "DEBUG" | "TRACE" => Some(Color::DarkGrey),
+        _ => None,
+    }
+}
+
+/// Envolve o texto do nível em códigos ANSI equivalentes às cores usadas na
+/// tabela (vermelho para ERROR, amarelo para WARNING, etc.), para manter a
+/// mesma paleta no modo `--follow`, que imprime linha a linha em vez de tabela.
+fn colorize_level_text(log_type: &str, enabled: bool) -> String {
+    if !enabled {
+        return log_type.to_string();
+    }
 
-            valid
+    let code = match log_type.to_uppercase().as_str() {
+        "ERROR" => "31",
+        "WARNING" | "WARN" => "33",
+        "INFO" => "32",
+        "DEBUG" | "TRACE" => "90",
+        _ => return log_type.to_string(),
+    };
+
+    format!("\x1b[{code}m{log_type}\x1b[0m")
+}
+
+/// Resolve `--log-path`/`--recursive` para um único arquivo concreto a ser
+/// acompanhado por `--follow`. Quando o padrão casa mais de um arquivo (glob,
+/// diretório ou um arquivo com irmãos rotacionados), segue o mais
+/// recentemente modificado, que é o que normalmente recebe as novas linhas.
+fn resolve_follow_target(log_path: &str, recursive: bool) -> io::Result<std::path::PathBuf> {
+    let mut files = resolve_log_files(log_path, recursive)?;
+
+    if files.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("--follow: nenhum arquivo encontrado em \"{log_path}\""),
+        ));
+    }
+
+    if files.len() == 1 {
+        return Ok(files.remove(0));
+    }
+
+    let newest = files
+        .into_iter()
+        .max_by_key(|path| {
+            std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
         })
-        .collect()
+        .expect("já verificamos que files não está vazio");
+
+    println!("--follow: \"{log_path}\" casa múltiplos arquivos, acompanhando o mais recente: {}", newest.display());
+
+    Ok(newest)
 }
 
+/// Implementa o modo `--follow`: posiciona-se no fim de `log_path`, faz
+/// polling por novas linhas, reabre o arquivo se ele encolher (indicativo de
+/// truncamento/rotação) e imprime incrementalmente as entradas que atendam
+/// ao `filter` já usado na análise inicial.
+fn follow_logs(log_path: &std::path::Path, format: &LogFormat, filter: &LogFilter, no_color: bool) -> io::Result<()> {
+    let colorize = !no_color
+        && std::env::var_os("NO_COLOR").is_none()
+        && io::stdout().is_terminal();
+
+    let mut file = File::open(log_path)?;
+    let mut last_len = file.metadata()?.len();
+    file.seek(SeekFrom::Start(last_len))?;
+    let mut reader = io::BufReader::new(file);
+
+    println!("Acompanhando {} em tempo real (Ctrl+C para interromper)...", log_path.display());
+
+    loop {
+        let current_len = File::open(log_path)?.metadata()?.len();
+        if current_len < last_len {
+            // Arquivo encolheu: provável rotação/truncamento, reabre do início.
+            let file = File::open(log_path)?;
+            reader = io::BufReader::new(file);
+            last_len = 0;
+        }
 
-fn display_logs(logs: Vec<&LogEntry>, output: Option<String>) {
-    let mut table = Table::new();
-    table.load_preset(UTF8_FULL);
-    table.set_header(vec!["DateTime", "Level", "Message"]);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let bytes_read = reader.read_line(&mut line)?;
+            if bytes_read == 0 {
+                break;
+            }
+            last_len += bytes_read as u64;
+
+            if let Some(entry) = parse_log_line(line.trim_end_matches(['\n', '\r']), format) {
+                if filter.matches(&entry) {
+                    println!(
+                        "{} [{}] {}",
+                        entry.timestamp,
+                        colorize_level_text(&entry.log_type, colorize),
+                        entry.message
+                    );
+                }
+            }
+        }
 
+        std::thread::sleep(Duration::from_millis(500));
+    }
+}
+
+fn render_logs_json(logs: &[&LogEntry]) -> String {
+    serde_json::to_string_pretty(logs).expect("Erro ao serializar logs em JSON")
+}
+
+fn render_logs_csv(logs: &[&LogEntry]) -> String {
+    let mut writer = csv::Writer::from_writer(Vec::new());
     for log in logs {
-        table.add_row(vec![
-            log.timestamp.to_string(),
-            log.log_type.clone(),
-            log.message.clone(),
-        ]);
+        writer.serialize(log).expect("Erro ao serializar log em CSV");
     }
+    String::from_utf8(writer.into_inner().expect("Erro ao finalizar CSV")).expect("CSV inválido")
+}
 
+/// Escreve `rendered` no arquivo de `--output`, se informado, ou na saída
+/// padrão caso contrário. Compartilhado por `display_logs`, `display_overview`
+/// e `display_stats` para que `--output` se aplique uniformemente a todos os
+/// subcomandos, não só a `Analyze`.
+fn write_rendered(rendered: &str, output: &Option<String>) {
     if let Some(out) = output {
         // Salva o output em um arquivo, caso especificado
         let mut file = OpenOptions::new()
@@ -182,11 +602,49 @@ fn display_logs(logs: Vec<&LogEntry>, output: Option<String>) {
             .open(out)
             .expect("Erro ao abrir arquivo de output");
 
-        writeln!(file, "{table}").expect("Erro ao escrever no arquivo de output");
+        writeln!(file, "{rendered}").expect("Erro ao escrever no arquivo de output");
     } else {
         // Exibe na saída padrão
-        println!("{table}");
-        println!("Número de registros encontrados: {}", table.row_count());
+        println!("{rendered}");
+    }
+}
+
+fn display_logs(logs: Vec<&LogEntry>, output: Option<String>, no_color: bool, format: OutputFormat) {
+    let row_count = logs.len();
+
+    let rendered = match format {
+        OutputFormat::Table => {
+            let colorize = should_colorize(no_color, &output);
+
+            let mut table = Table::new();
+            table.load_preset(UTF8_FULL);
+            table.set_header(vec!["DateTime", "Level", "Message"]);
+
+            for log in &logs {
+                let mut level_cell = Cell::new(&log.log_type);
+                if colorize {
+                    if let Some(color) = level_color(&log.log_type) {
+                        level_cell = level_cell.fg(color).add_attribute(Attribute::Bold);
+                    }
+                }
+
+                table.add_row(vec![
+                    Cell::new(log.timestamp.to_string()),
+                    level_cell,
+                    Cell::new(&log.message),
+                ]);
+            }
+
+            table.to_string()
+        }
+        OutputFormat::Json => render_logs_json(&logs),
+        OutputFormat::Csv => render_logs_csv(&logs),
+    };
+
+    let printed_to_stdout = output.is_none();
+    write_rendered(&rendered, &output);
+    if printed_to_stdout && format == OutputFormat::Table {
+        println!("Número de registros encontrados: {}", row_count);
     }
 }
 
@@ -201,14 +659,313 @@ fn summarize_logs(logs: &[LogEntry]) -> HashMap<String, usize> {
     summary
 }
 
-fn display_overview(summary: HashMap<String, usize>) {
-    let mut table = Table::new();
-    table.load_preset(UTF8_FULL);
-    table.set_header(vec!["Log Level", "Count"]);
+fn display_overview(summary: HashMap<String, usize>, format: OutputFormat, output: Option<String>) {
+    let rendered = match format {
+        OutputFormat::Table => {
+            let mut table = Table::new();
+            table.load_preset(UTF8_FULL);
+            table.set_header(vec!["Log Level", "Count"]);
+
+            for (level, count) in summary {
+                table.add_row(vec![level, count.to_string()]);
+            }
+
+            table.to_string()
+        }
+        OutputFormat::Json => {
+            serde_json::to_string_pretty(&summary).expect("Erro ao serializar resumo em JSON")
+        }
+        OutputFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer.write_record(["level", "count"]).expect("Erro ao escrever cabeçalho CSV");
+            for (level, count) in &summary {
+                writer.write_record([level, &count.to_string()]).expect("Erro ao escrever registro CSV");
+            }
+            String::from_utf8(writer.into_inner().expect("Erro ao finalizar CSV")).expect("CSV inválido")
+        }
+    };
+
+    write_rendered(&rendered, &output);
+}
+
+/// Interpreta um tamanho de intervalo como "15m", "1h" ou "1d" em uma
+/// `chrono::Duration`, usada para truncar timestamps no histograma do `Stats`.
+fn parse_bucket(spec: &str) -> Option<chrono::Duration> {
+    let spec = spec.trim();
+    let split_at = spec.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = spec.split_at(split_at);
+    let amount: i64 = amount.parse().ok()?;
+
+    match unit {
+        "m" => Some(chrono::Duration::minutes(amount)),
+        "h" => Some(chrono::Duration::hours(amount)),
+        "d" => Some(chrono::Duration::days(amount)),
+        _ => None,
+    }
+}
+
+fn truncate_to_bucket(timestamp: NaiveDateTime, bucket: chrono::Duration) -> NaiveDateTime {
+    let bucket_secs = bucket.num_seconds().max(1);
+    let epoch_secs = timestamp.and_utc().timestamp();
+    let bucket_start_secs = (epoch_secs.div_euclid(bucket_secs)) * bucket_secs;
+    chrono::DateTime::from_timestamp(bucket_start_secs, 0)
+        .expect("timestamp de bucket inválido")
+        .naive_utc()
+}
+
+/// Normaliza uma mensagem substituindo UUIDs por `<uuid>` e sequências de
+/// dígitos (ids, contadores, timestamps embutidos) por `<n>`, para que ocorrências semelhantes de um
+/// mesmo erro colapsem na contagem de mensagens mais frequentes.
+fn normalize_message(message: &str) -> String {
+    static UUID_RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    static DIGITS_RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+
+    let uuid_re = UUID_RE.get_or_init(|| {
+        Regex::new(r"(?i)[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}").unwrap()
+    });
+    let digits_re = DIGITS_RE.get_or_init(|| Regex::new(r"\d+").unwrap());
+
+    let without_uuids = uuid_re.replace_all(message, "<uuid>");
+    digits_re.replace_all(&without_uuids, "<n>").into_owned()
+}
+
+/// Contagem por nível dentro de um bucket, usada para expor a tendência de
+/// cada severidade (não só ERROR) ao longo do tempo.
+#[derive(Debug, Default, Clone, Serialize)]
+struct LevelCounts {
+    trace: usize,
+    debug: usize,
+    info: usize,
+    warning: usize,
+    error: usize,
+    /// Entradas cujo nível não é reconhecido por `LogLevel::from_log_type`.
+    other: usize,
+}
+
+impl LevelCounts {
+    fn bump(&mut self, level: Option<LogLevel>) {
+        match level {
+            Some(LogLevel::Trace) => self.trace += 1,
+            Some(LogLevel::Debug) => self.debug += 1,
+            Some(LogLevel::Info) => self.info += 1,
+            Some(LogLevel::Warning) => self.warning += 1,
+            Some(LogLevel::Error) => self.error += 1,
+            None => self.other += 1,
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.trace + self.debug + self.info + self.warning + self.error + self.other
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct BucketStat {
+    #[serde(serialize_with = "serialize_rfc3339")]
+    bucket_start: NaiveDateTime,
+    total: usize,
+    counts: LevelCounts,
+    /// Proporção de ERROR sobre o total do bucket, para leitura rápida;
+    /// `counts` traz o detalhamento por nível para quem precisa da série completa.
+    error_rate: f64,
+}
 
-    for (level, count) in summary {
-        table.add_row(vec![level, count.to_string()]);
+#[derive(Debug, Serialize)]
+struct MessageStat {
+    message: String,
+    count: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct StatsReport {
+    histogram: Vec<BucketStat>,
+    top_messages: Vec<MessageStat>,
+}
+
+fn compute_stats(logs: &[LogEntry], bucket: chrono::Duration, top_n: usize) -> StatsReport {
+    let mut buckets: std::collections::BTreeMap<NaiveDateTime, LevelCounts> = std::collections::BTreeMap::new();
+    let mut message_counts: HashMap<String, usize> = HashMap::new();
+
+    for log in logs {
+        let bucket_start = truncate_to_bucket(log.timestamp, bucket);
+        buckets.entry(bucket_start).or_default().bump(LogLevel::from_log_type(&log.log_type));
+
+        *message_counts.entry(normalize_message(&log.message)).or_insert(0) += 1;
     }
 
-    println!("{table}");
+    let histogram = buckets
+        .into_iter()
+        .map(|(bucket_start, counts)| {
+            let total = counts.total();
+            BucketStat {
+                bucket_start,
+                total,
+                error_rate: if total == 0 { 0.0 } else { counts.error as f64 / total as f64 },
+                counts,
+            }
+        })
+        .collect();
+
+    let mut top_messages: Vec<MessageStat> = message_counts
+        .into_iter()
+        .map(|(message, count)| MessageStat { message, count })
+        .collect();
+    top_messages.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.message.cmp(&b.message)));
+    top_messages.truncate(top_n);
+
+    StatsReport { histogram, top_messages }
+}
+
+fn display_stats(report: StatsReport, format: OutputFormat, output: Option<String>) {
+    let rendered = match format {
+        OutputFormat::Table => {
+            let mut histogram_table = Table::new();
+            histogram_table.load_preset(UTF8_FULL);
+            histogram_table.set_header(vec![
+                "Bucket", "Total", "Trace", "Debug", "Info", "Warning", "Error", "Error Rate",
+            ]);
+            for bucket in &report.histogram {
+                histogram_table.add_row(vec![
+                    bucket.bucket_start.to_string(),
+                    bucket.total.to_string(),
+                    bucket.counts.trace.to_string(),
+                    bucket.counts.debug.to_string(),
+                    bucket.counts.info.to_string(),
+                    bucket.counts.warning.to_string(),
+                    bucket.counts.error.to_string(),
+                    format!("{:.1}%", bucket.error_rate * 100.0),
+                ]);
+            }
+
+            let mut messages_table = Table::new();
+            messages_table.load_preset(UTF8_FULL);
+            messages_table.set_header(vec!["Message", "Count"]);
+            for message in &report.top_messages {
+                messages_table.add_row(vec![message.message.clone(), message.count.to_string()]);
+            }
+
+            format!("{histogram_table}\n{messages_table}")
+        }
+        OutputFormat::Json => {
+            serde_json::to_string_pretty(&report).expect("Erro ao serializar estatísticas em JSON")
+        }
+        OutputFormat::Csv => {
+            // As duas seções têm números de colunas diferentes (8 e 2).
+            let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(Vec::new());
+            writer
+                .write_record(["bucket", "total", "trace", "debug", "info", "warning", "error", "error_rate"])
+                .expect("Erro ao escrever cabeçalho CSV");
+            for bucket in &report.histogram {
+                writer
+                    .write_record([
+                        bucket.bucket_start.and_utc().to_rfc3339(),
+                        bucket.total.to_string(),
+                        bucket.counts.trace.to_string(),
+                        bucket.counts.debug.to_string(),
+                        bucket.counts.info.to_string(),
+                        bucket.counts.warning.to_string(),
+                        bucket.counts.error.to_string(),
+                        bucket.error_rate.to_string(),
+                    ])
+                    .expect("Erro ao escrever registro CSV");
+            }
+            writer.write_record(["", "", "", "", "", "", "", ""]).ok();
+            writer.write_record(["message", "count"]).expect("Erro ao escrever cabeçalho CSV");
+            for message in &report.top_messages {
+                writer
+                    .write_record([message.message.clone(), message.count.to_string()])
+                    .expect("Erro ao escrever registro CSV");
+            }
+            String::from_utf8(writer.into_inner().expect("Erro ao finalizar CSV")).expect("CSV inválido")
+        }
+    };
+
+    write_rendered(&rendered, &output);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_timestamp_augments_year_when_format_has_none() {
+        let current_year = chrono::Local::now().year();
+        let parsed = parse_timestamp("Oct 11 22:14:15", "%b %e %H:%M:%S").unwrap();
+        assert_eq!(parsed.year(), current_year);
+        assert_eq!(parsed.format("%b %e %H:%M:%S").to_string(), "Oct 11 22:14:15");
+    }
+
+    #[test]
+    fn parse_timestamp_respects_year_already_present_in_format() {
+        let parsed = parse_timestamp("11/10/2020 22:14", "%d/%m/%Y %H:%M").unwrap();
+        assert_eq!(parsed.year(), 2020);
+    }
+
+    #[test]
+    fn normalize_short_level_maps_glog_codes() {
+        assert_eq!(normalize_short_level("I"), "INFO");
+        assert_eq!(normalize_short_level("W"), "WARNING");
+        assert_eq!(normalize_short_level("E"), "ERROR");
+        assert_eq!(normalize_short_level("F"), "ERROR");
+        assert_eq!(normalize_short_level("D"), "DEBUG");
+        assert_eq!(normalize_short_level("T"), "TRACE");
+        assert_eq!(normalize_short_level("X"), "X");
+    }
+
+    #[test]
+    fn log_level_from_log_type_recognizes_known_levels_case_insensitively() {
+        assert_eq!(LogLevel::from_log_type("error"), Some(LogLevel::Error));
+        assert_eq!(LogLevel::from_log_type("WARNING"), Some(LogLevel::Warning));
+        assert_eq!(LogLevel::from_log_type("Warn"), Some(LogLevel::Warning));
+        assert_eq!(LogLevel::from_log_type("trace"), Some(LogLevel::Trace));
+        assert_eq!(LogLevel::from_log_type("bogus"), None);
+    }
+
+    #[test]
+    fn log_level_ordering_supports_min_level_threshold() {
+        assert!(LogLevel::Trace < LogLevel::Debug);
+        assert!(LogLevel::Debug < LogLevel::Info);
+        assert!(LogLevel::Info < LogLevel::Warning);
+        assert!(LogLevel::Warning < LogLevel::Error);
+        assert!(LogLevel::Error >= LogLevel::Warning);
+    }
+
+    #[test]
+    fn rotated_suffix_regex_matches_tikv_style_suffixes() {
+        let re = rotated_suffix_regex();
+        assert!(re.is_match("app.log.2020-05-11-13:22:01.123"));
+        assert!(re.is_match("app.log.2020-05-11-13:22:01"));
+        assert!(!re.is_match("app.log"));
+        assert!(!re.is_match("app.log.1"));
+    }
+
+    #[test]
+    fn parse_bucket_accepts_minutes_hours_and_days() {
+        assert_eq!(parse_bucket("15m"), Some(chrono::Duration::minutes(15)));
+        assert_eq!(parse_bucket("1h"), Some(chrono::Duration::hours(1)));
+        assert_eq!(parse_bucket("1d"), Some(chrono::Duration::days(1)));
+    }
+
+    #[test]
+    fn parse_bucket_rejects_unknown_units_and_garbage() {
+        assert_eq!(parse_bucket("15x"), None);
+        assert_eq!(parse_bucket("bogus"), None);
+        assert_eq!(parse_bucket(""), None);
+    }
+
+    #[test]
+    fn truncate_to_bucket_rounds_down_to_bucket_start() {
+        let timestamp = NaiveDateTime::parse_from_str("2024-01-01 13:47:32", "%Y-%m-%d %H:%M:%S").unwrap();
+        let truncated = truncate_to_bucket(timestamp, chrono::Duration::hours(1));
+        assert_eq!(truncated.format("%Y-%m-%d %H:%M:%S").to_string(), "2024-01-01 13:00:00");
+    }
+
+    #[test]
+    fn normalize_message_collapses_uuids_and_digits() {
+        assert_eq!(
+            normalize_message("request 123 failed for user 550e8400-e29b-41d4-a716-446655440000"),
+            "request <n> failed for user <uuid>"
+        );
+        assert_eq!(normalize_message("no dynamic parts here"), "no dynamic parts here");
+    }
 }